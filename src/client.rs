@@ -10,7 +10,7 @@
 //!
 //! Every method will return a Result<Response, Error>
 //! ```rust
-//! # let result: Result<&str, String> = Ok("test");
+//! # let result: Result<&str, &str> = Ok("test");
 //! if result.is_ok() {
 //!    // the method completed with success
 //! } else {
@@ -18,18 +18,45 @@
 //! }
 //! ```
 
+use crate::digest::{self, DigestChallenge};
 use super::prelude::*;
+use futures_util::TryStreamExt;
 use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_util::io::{ReaderStream, StreamReader};
+
+/// How the client authenticates each request.
+#[derive(Debug)]
+enum AuthMode {
+    Basic,
+    Digest(Mutex<DigestState>),
+}
+
+#[derive(Debug, Default)]
+struct DigestState {
+    challenge: Option<DigestChallenge>,
+    nonce_count: u32,
+}
+
+/// Exponential backoff policy applied to retryable requests; see [`Client::with_retries`].
+#[derive(Debug, Clone, Copy)]
+struct RetryPolicy {
+    max_attempts: u32,
+}
 
 #[derive(Debug)]
 pub struct Client {
     username: String,
     password: String,
     client: reqwest::Client,
+    auth: AuthMode,
+    retry_policy: Option<RetryPolicy>,
 }
 
 impl Client {
-    /// Initialization of the client
+    /// Initialization of the client, authenticating with HTTP Basic auth
     ///
     /// Initialized client will be stored for future requests
     pub fn init(username: &str, password: &str) -> Self {
@@ -37,9 +64,43 @@ impl Client {
             username: username.to_owned(),
             password: password.to_owned(),
             client: reqwest::Client::new(),
+            auth: AuthMode::Basic,
+            retry_policy: None,
         }
     }
 
+    /// Initialization of the client, authenticating with HTTP Digest auth
+    ///
+    /// The client's first request authenticates with an extra round-trip: it is sent
+    /// without credentials, the server's `WWW-Authenticate: Digest` challenge on the resulting
+    /// 401 is parsed and cached, and the request is retried with the computed `Authorization`
+    /// header. Later requests reuse the cached challenge and just bump the `nc` counter.
+    ///
+    /// Only the `MD5` and `MD5-sess` `algorithm`s and the `qop=auth` subset of RFC 7616 are
+    /// supported; a server asking for anything else (e.g. `SHA-256`) fails the request with
+    /// [`Error::Auth`] instead of authenticating.
+    pub fn init_digest(username: &str, password: &str) -> Self {
+        Client {
+            username: username.to_owned(),
+            password: password.to_owned(),
+            client: reqwest::Client::new(),
+            auth: AuthMode::Digest(Mutex::new(DigestState::default())),
+            retry_policy: None,
+        }
+    }
+
+    /// Enables automatic retries with exponential backoff for transient failures
+    ///
+    /// Idempotent requests (GET, PROPFIND, MKCOL, DELETE, MOVE) retry by default once this is
+    /// set; PUT does not, since its body may not be replayable — use
+    /// [`Client::put_with_retry`] to opt in. A retryable outcome is a 5xx/429 response or a
+    /// connect/timeout error; the delay starts at 1s, doubles each attempt, is capped at 30s
+    /// plus a little jitter, and honors a `Retry-After` header when the server sends one.
+    pub fn with_retries(mut self, max_attempts: u32) -> Self {
+        self.retry_policy = Some(RetryPolicy { max_attempts });
+        self
+    }
+
     fn custom_header(&self, name: &str, value: &str) -> header::HeaderMap {
         let mut headers = header::HeaderMap::new();
         headers.insert(
@@ -56,18 +117,246 @@ impl Client {
         params
     }
 
-    /// Main function that creates the RequestBuilder, sets the method, url and the basic_auth
-    fn start_request(&self, method: Method, path: &str) -> RequestBuilder {
-        self.client
-            .request(method, Url::parse(path).unwrap())
-            .basic_auth(self.username.as_str(), Some(self.password.as_str()))
+    /// Main function that creates the RequestBuilder and sets the method and url
+    ///
+    /// Authentication is applied later by [`Client::send`], since Digest auth needs to see
+    /// the method/path pair and may have to resend the request.
+    fn start_request(&self, method: Method, path: &str) -> Result<RequestBuilder, Error> {
+        Ok(self.client.request(method, Url::parse(path)?))
+    }
+
+    /// Sends a request built by [`Client::start_request`], retrying on transient failures if
+    /// `retryable` and a retry policy is set via [`Client::with_retries`]
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        builder: RequestBuilder,
+        retryable: bool,
+    ) -> Result<Response, Error> {
+        let max_attempts = if retryable {
+            self.retry_policy.map_or(1, |policy| policy.max_attempts)
+        } else {
+            1
+        };
+
+        let mut attempt = 1u32;
+        let mut current = builder;
+        loop {
+            let retry_builder = if attempt < max_attempts {
+                current.try_clone()
+            } else {
+                None
+            };
+
+            let result = self.send_authenticated(method.clone(), path, current).await;
+
+            let should_retry = match (&result, &retry_builder) {
+                (Ok(response), Some(_)) => Self::is_retryable_status(response.status()),
+                (Err(err), Some(_)) => Self::is_retryable_error(err),
+                _ => false,
+            };
+
+            if !should_retry {
+                return result;
+            }
+
+            let retry_after = result.as_ref().ok().and_then(|r| r.headers().get(header::RETRY_AFTER));
+            tokio::time::sleep(Self::backoff_delay(attempt, retry_after)).await;
+            attempt += 1;
+            current = retry_builder.unwrap();
+        }
+    }
+
+    /// Sends a request like [`Client::send`], additionally turning a 4xx/5xx response into
+    /// `Err(Error::InvalidStatus)` instead of handing the caller an `Ok(Response)` they have to
+    /// inspect themselves. [`Client::raw_response`] is the escape hatch that skips this.
+    async fn checked_send(
+        &self,
+        method: Method,
+        path: &str,
+        builder: RequestBuilder,
+        retryable: bool,
+    ) -> Result<Response, Error> {
+        let response = self.send(method, path, builder, retryable).await?;
+        let code = response.status();
+        if code.is_client_error() || code.is_server_error() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(Error::InvalidStatus { code, body });
+        }
+        Ok(response)
+    }
+
+    fn is_retryable_status(status: reqwest::StatusCode) -> bool {
+        status.is_server_error() || status == reqwest::StatusCode::TOO_MANY_REQUESTS
+    }
+
+    fn is_retryable_error(err: &Error) -> bool {
+        matches!(err, Error::Transport(e) if e.is_connect() || e.is_timeout())
+    }
+
+    /// `attempt` is 1-based: 1s, 2s, 4s, ... capped at 30s, plus up to 500ms of jitter, unless
+    /// the response carried a `Retry-After` header, in which case that is honored instead.
+    fn backoff_delay(attempt: u32, retry_after: Option<&header::HeaderValue>) -> std::time::Duration {
+        if let Some(secs) = retry_after
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok())
+        {
+            return std::time::Duration::from_secs(secs);
+        }
+
+        let base_secs = 1u64.checked_shl(attempt - 1).unwrap_or(u64::MAX).min(30);
+        let jitter_ms = rand::random::<u64>() % 500;
+        std::time::Duration::from_millis(base_secs * 1000 + jitter_ms)
+    }
+
+    /// Applies Basic or Digest auth to a request and sends it
+    ///
+    /// For Digest auth, the client's first request is sent unauthenticated so the server's
+    /// `WWW-Authenticate` challenge can be read off the resulting 401; the request is then
+    /// retried with the computed `Authorization` header. Later requests reuse the cached
+    /// challenge and skip straight to sending with a bumped `nc`.
+    async fn send_authenticated(
+        &self,
+        method: Method,
+        path: &str,
+        builder: RequestBuilder,
+    ) -> Result<Response, Error> {
+        match &self.auth {
+            AuthMode::Basic => {
+                builder
+                    .basic_auth(self.username.as_str(), Some(self.password.as_str()))
+                    .send()
+                    .await
+                    .map_err(Error::from)
+            }
+            AuthMode::Digest(state) => self.send_digest(method, path, builder, state).await,
+        }
+    }
+
+    async fn send_digest(
+        &self,
+        method: Method,
+        path: &str,
+        builder: RequestBuilder,
+        state: &Mutex<DigestState>,
+    ) -> Result<Response, Error> {
+        let parsed = Url::parse(path)?;
+        let uri = match parsed.query() {
+            Some(query) => format!("{}?{query}", parsed.path()),
+            None => parsed.path().to_string(),
+        };
+        let uri = uri.as_str();
+
+        let cached_challenge = state.lock().unwrap().challenge.clone();
+        if let Some(challenge) = cached_challenge {
+            let nc = {
+                let mut state = state.lock().unwrap();
+                state.nonce_count += 1;
+                state.nonce_count
+            };
+            let authorization = digest::authorization_header(
+                &challenge,
+                &self.username,
+                &self.password,
+                method.as_str(),
+                uri,
+                nc,
+                &digest::client_nonce(),
+            )?;
+            return builder
+                .header(header::AUTHORIZATION, authorization)
+                .send()
+                .await
+                .map_err(Error::from);
+        }
+
+        // No cached challenge yet: probe with an unauthenticated request.
+        let Some(probe_builder) = builder.try_clone() else {
+            return Err(Error::Auth(
+                "request body does not support retrying, required for the initial Digest auth probe".to_string(),
+            ));
+        };
+        let probe = probe_builder.send().await?;
+        if probe.status() != reqwest::StatusCode::UNAUTHORIZED {
+            return Ok(probe);
+        }
+
+        let challenge = probe
+            .headers()
+            .get(header::WWW_AUTHENTICATE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(DigestChallenge::parse);
+        let Some(challenge) = challenge else {
+            return Ok(probe);
+        };
+
+        let authorization = digest::authorization_header(
+            &challenge,
+            &self.username,
+            &self.password,
+            method.as_str(),
+            uri,
+            1,
+            &digest::client_nonce(),
+        )?;
+        *state.lock().unwrap() = DigestState {
+            challenge: Some(challenge),
+            nonce_count: 1,
+        };
+
+        builder
+            .header(header::AUTHORIZATION, authorization)
+            .send()
+            .await
+            .map_err(Error::from)
     }
 
     /// Get a file from Webdav server
     ///
     /// Use absolute path to the webdav server file location
     pub async fn get(&self, path: &str) -> Result<Response, Error> {
-        self.start_request(Method::GET, path).send().await
+        let builder = self.start_request(Method::GET, path)?;
+        self.checked_send(Method::GET, path, builder, true).await
+    }
+
+    /// Downloads a file from Webdav server straight into `writer`, chunk-by-chunk
+    ///
+    /// Unlike [`Client::get`], the response body is never buffered in full, so multi-gigabyte
+    /// resources can be downloaded with bounded memory. Returns the number of bytes written. If
+    /// the response carries a recognized `Content-Encoding` (currently just gzip), the body is
+    /// transparently decompressed as it streams through.
+    pub async fn get_to_writer<W: AsyncWrite + Unpin>(
+        &self,
+        path: &str,
+        writer: &mut W,
+    ) -> Result<u64, Error> {
+        let response = self.get(path).await?;
+
+        let codec = response
+            .headers()
+            .get(header::CONTENT_ENCODING)
+            .and_then(|value| value.to_str().ok())
+            .and_then(Compression::from_content_encoding);
+
+        let byte_stream = response.bytes_stream().map_err(std::io::Error::other);
+        let body_reader = StreamReader::new(byte_stream);
+
+        let mut reader: std::pin::Pin<Box<dyn AsyncRead + Send>> = match codec {
+            Some(codec) => codec.decode(body_reader),
+            None => Box::pin(body_reader),
+        };
+
+        Ok(tokio::io::copy(&mut reader, writer).await?)
+    }
+
+    /// Downloads a file from Webdav server straight to `dest` on disk
+    ///
+    /// Convenience wrapper around [`Client::get_to_writer`] for the common case of saving to a
+    /// local file. Returns the number of bytes written.
+    pub async fn download_file(&self, path: &str, dest: &Path) -> Result<u64, Error> {
+        let mut file = tokio::fs::File::create(dest).await?;
+        self.get_to_writer(path, &mut file).await
     }
 
     /// Upload a file/zip on Webdav server
@@ -77,37 +366,109 @@ impl Client {
     ///
     /// Use absolute path to the webdav server folder location
     pub async fn put<B: Into<Body>>(&self, body: B, path: &str) -> Result<Response, Error> {
-        self.start_request(Method::PUT, path)
+        self.put_maybe_locked(body, path, None, false).await
+    }
+
+    /// Upload a file/zip on Webdav server, presenting a lock token obtained from [`Client::lock`]
+    ///
+    /// Use this against a resource locked with [`Client::lock`]; the server rejects the write
+    /// otherwise.
+    pub async fn put_locked<B: Into<Body>>(
+        &self,
+        body: B,
+        path: &str,
+        lock_token: &str,
+    ) -> Result<Response, Error> {
+        self.put_maybe_locked(body, path, Some(lock_token), false).await
+    }
+
+    /// Upload a file/zip on Webdav server, retrying on transient failures
+    ///
+    /// PUT doesn't retry by default since `B` may be a stream that can't be replayed; opt in
+    /// here only when `body` is safe to resend (e.g. an in-memory buffer).
+    pub async fn put_with_retry<B: Into<Body>>(&self, body: B, path: &str) -> Result<Response, Error> {
+        self.put_maybe_locked(body, path, None, true).await
+    }
+
+    /// Upload a file/zip on Webdav server, gzip-encoding the body as it streams up
+    ///
+    /// `reader` is compressed on the fly rather than buffered in full, so memory stays bounded
+    /// for large uploads. This cuts bandwidth noticeably for text-heavy trees (logs, XML,
+    /// source).
+    pub async fn put_compressed<R>(
+        &self,
+        reader: R,
+        path: &str,
+        codec: Compression,
+    ) -> Result<Response, Error>
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        let encoded = codec.encode(reader);
+        let body = Body::wrap_stream(ReaderStream::new(encoded));
+
+        let builder = self
+            .start_request(Method::PUT, path)?
             .headers(self.custom_header("content-type", "application/octet-stream"))
-            .body(body)
-            .send()
-            .await
+            .headers(self.custom_header("content-encoding", codec.content_encoding()))
+            .body(body);
+        self.checked_send(Method::PUT, path, builder, false).await
+    }
+
+    async fn put_maybe_locked<B: Into<Body>>(
+        &self,
+        body: B,
+        path: &str,
+        lock_token: Option<&str>,
+        retryable: bool,
+    ) -> Result<Response, Error> {
+        let mut builder = self
+            .start_request(Method::PUT, path)?
+            .headers(self.custom_header("content-type", "application/octet-stream"))
+            .body(body);
+        if let Some(token) = lock_token {
+            builder = builder.headers(self.custom_header("If", &format!("(<{token}>)")));
+        }
+        self.checked_send(Method::PUT, path, builder, retryable).await
     }
 
     /// Deletes the collection, file, folder or zip archive at the given path on Webdav server
     ///
     /// Use absolute path to the webdav server file location
     pub async fn delete(&self, path: &str) -> Result<Response, Error> {
-        self.start_request(Method::DELETE, path).send().await
+        self.delete_maybe_locked(path, None).await
+    }
+
+    /// Deletes the resource at `path`, presenting a lock token obtained from [`Client::lock`]
+    pub async fn delete_locked(&self, path: &str, lock_token: &str) -> Result<Response, Error> {
+        self.delete_maybe_locked(path, Some(lock_token)).await
+    }
+
+    async fn delete_maybe_locked(&self, path: &str, lock_token: Option<&str>) -> Result<Response, Error> {
+        let mut builder = self.start_request(Method::DELETE, path)?;
+        if let Some(token) = lock_token {
+            builder = builder.headers(self.custom_header("If", &format!("(<{token}>)")));
+        }
+        self.checked_send(Method::DELETE, path, builder, true).await
     }
 
     /// Unzips the .zip archieve on Webdav server
     ///
     /// Use absolute path to the webdav server file location
     pub async fn unzip(&self, path: &str) -> Result<Response, Error> {
-        self.start_request(Method::POST, path)
-            .form(&self.form_params("method", "UNZIP"))
-            .send()
-            .await
+        let builder = self
+            .start_request(Method::POST, path)?
+            .form(&self.form_params("method", "UNZIP"));
+        self.checked_send(Method::POST, path, builder, false).await
     }
 
     /// Creates a directory on Webdav server
     ///
     /// Use absolute path to the webdav server file location
     pub async fn mkcol(&self, path: &str) -> Result<Response, Error> {
-        self.start_request(Method::from_bytes(b"MKCOL").unwrap(), path)
-            .send()
-            .await
+        let method = Method::from_bytes(b"MKCOL").unwrap();
+        let builder = self.start_request(method.clone(), path)?;
+        self.checked_send(method, path, builder, true).await
     }
 
     /// Rename or move a collection, file, folder on Webdav server
@@ -116,10 +477,108 @@ impl Client {
     ///
     /// Use absolute path to the webdav server file location
     pub async fn mv(&self, from: &str, to: &str) -> Result<Response, Error> {
-        self.start_request(Method::from_bytes(b"MOVE").unwrap(), from)
-            .headers(self.custom_header("destination", to))
-            .send()
-            .await
+        self.mv_maybe_locked(from, to, None).await
+    }
+
+    /// Rename or move a resource, presenting a lock token obtained from [`Client::lock`]
+    pub async fn mv_locked(&self, from: &str, to: &str, lock_token: &str) -> Result<Response, Error> {
+        self.mv_maybe_locked(from, to, Some(lock_token)).await
+    }
+
+    async fn mv_maybe_locked(
+        &self,
+        from: &str,
+        to: &str,
+        lock_token: Option<&str>,
+    ) -> Result<Response, Error> {
+        let method = Method::from_bytes(b"MOVE").unwrap();
+        let mut builder = self
+            .start_request(method.clone(), from)?
+            .headers(self.custom_header("destination", to));
+        if let Some(token) = lock_token {
+            builder = builder.headers(self.custom_header("If", &format!("(<{token}>)")));
+        }
+        self.checked_send(method, from, builder, true).await
+    }
+
+    /// Locks a resource with an exclusive write lock, returning its opaque lock token
+    ///
+    /// `timeout_secs` is the lock lifetime requested from the server; `depth` follows the same
+    /// convention as [`Client::list`]. The returned token must be passed to [`Client::unlock`]
+    /// to release the lock, or into `_locked` write methods to operate on the locked resource.
+    pub async fn lock(
+        &self,
+        path: &str,
+        owner: &str,
+        timeout_secs: u64,
+        depth: &str,
+    ) -> Result<String, Error> {
+        let body = format!(
+            r#"<?xml version="1.0" encoding="utf-8" ?>
+            <D:lockinfo xmlns:D="DAV:">
+                <D:lockscope><D:exclusive/></D:lockscope>
+                <D:locktype><D:write/></D:locktype>
+                <D:owner><D:href>{owner}</D:href></D:owner>
+            </D:lockinfo>
+        "#
+        );
+
+        let method = Method::from_bytes(b"LOCK").unwrap();
+        let builder = self
+            .start_request(method.clone(), path)?
+            .headers(self.custom_header("depth", depth))
+            .headers(self.custom_header("timeout", &format!("Second-{timeout_secs}")))
+            .body(body);
+
+        let response = self.checked_send(method, path, builder, false).await?;
+        let xml = response.text().await?;
+        crate::file::parse_lock_token(&xml)
+            .ok_or_else(|| Error::Xml("server did not return a lock token".to_string()))
+    }
+
+    /// Releases a lock previously obtained with [`Client::lock`]
+    pub async fn unlock(&self, path: &str, lock_token: &str) -> Result<Response, Error> {
+        let method = Method::from_bytes(b"UNLOCK").unwrap();
+        let builder = self
+            .start_request(method.clone(), path)?
+            .headers(self.custom_header("lock-token", &format!("<{lock_token}>")));
+        self.checked_send(method, path, builder, false).await
+    }
+
+    /// Sets and/or removes custom dead properties on a resource via PROPPATCH
+    ///
+    /// `set` is a list of `(property name, value)` pairs to set, `remove` a list of property
+    /// names to remove; property names are written verbatim into the request body, so callers
+    /// wanting a namespace should include it (e.g. `"x:author"` alongside an `xmlns:x` the
+    /// server already knows about).
+    pub async fn proppatch(
+        &self,
+        path: &str,
+        set: &[(&str, &str)],
+        remove: &[&str],
+    ) -> Result<Response, Error> {
+        let mut body = String::from(
+            r#"<?xml version="1.0" encoding="utf-8" ?><D:propertyupdate xmlns:D="DAV:">"#,
+        );
+        if !set.is_empty() {
+            body.push_str("<D:set><D:prop>");
+            for (name, value) in set {
+                body.push_str(&format!("<{name}>{value}</{name}>"));
+            }
+            body.push_str("</D:prop></D:set>");
+        }
+        if !remove.is_empty() {
+            body.push_str("<D:remove><D:prop>");
+            for name in remove {
+                body.push_str(&format!("<{name}/>"));
+            }
+            body.push_str("</D:prop></D:remove>");
+        }
+        body.push_str("</D:propertyupdate>");
+
+        let method = Method::from_bytes(b"PROPPATCH").unwrap();
+        let builder = self.start_request(method.clone(), path)?.body(body);
+        self.checked_send(method, path, builder, false).await
     }
 
     /// List files and folders at the given path on Webdav server
@@ -135,11 +594,32 @@ impl Client {
             </D:propfind>
         "#;
 
-        self.start_request(Method::from_bytes(b"PROPFIND").unwrap(), path)
+        let method = Method::from_bytes(b"PROPFIND").unwrap();
+        let builder = self
+            .start_request(method.clone(), path)?
             .headers(self.custom_header("depth", depth))
-            .body(body)
-            .send()
-            .await
+            .body(body);
+        self.checked_send(method, path, builder, true).await
+    }
+
+    /// List files and folders at the given path on Webdav server, parsed into `FileInfo`s
+    ///
+    /// Same request as [`Client::list`], but runs the PROPFIND response through
+    /// [`crate::file::parse_xml`] so callers don't have to pull the raw body out themselves.
+    pub async fn list_parsed(&self, path: &str, depth: &str) -> Result<Vec<FileInfo>, Error> {
+        let response = self.list(path, depth).await?;
+        let body = response.text().await?;
+        crate::file::parse_xml(&body)
+    }
+
+    /// Sends a request without checking the response status, for callers who want to inspect
+    /// a non-2xx `Response` themselves instead of getting an [`Error::InvalidStatus`]
+    ///
+    /// Every other method on `Client` turns a 4xx/5xx response into `Err(Error::InvalidStatus)`;
+    /// this bypasses that check.
+    pub async fn raw_response(&self, method: Method, path: &str) -> Result<Response, Error> {
+        let builder = self.start_request(method.clone(), path)?;
+        self.send(method, path, builder, false).await
     }
 }
 
@@ -231,4 +711,60 @@ mod tests {
 
         assert_eq!(result.is_ok(), true);
     }
+
+    #[tokio::test]
+    async fn test_8_lock_unlock() {
+        let webdav_client = get_client();
+        let path = get_server_path("rustydav/lock-test.txt");
+        webdav_client.put("locked", path.as_str()).await.unwrap();
+
+        let lock_result = webdav_client
+            .lock(path.as_str(), "rustydav", 60, "0")
+            .await;
+        assert_eq!(lock_result.is_ok(), true);
+
+        let token = lock_result.unwrap();
+        let unlock_result = webdav_client.unlock(path.as_str(), token.as_str()).await;
+        assert_eq!(unlock_result.is_ok(), true);
+    }
+
+    #[test]
+    fn backoff_delay_doubles_each_attempt_up_to_the_cap() {
+        let secs = |attempt| Client::backoff_delay(attempt, None).as_secs();
+
+        assert_eq!(secs(1), 1);
+        assert_eq!(secs(2), 2);
+        assert_eq!(secs(3), 4);
+        assert_eq!(secs(4), 8);
+        assert_eq!(secs(5), 16);
+        assert_eq!(secs(6), 30);
+        assert_eq!(secs(10), 30);
+    }
+
+    #[test]
+    fn backoff_delay_adds_up_to_500ms_of_jitter() {
+        let delay = Client::backoff_delay(1, None);
+        assert!(delay.as_millis() >= 1000 && delay.as_millis() < 1500);
+    }
+
+    #[test]
+    fn backoff_delay_honors_retry_after_header() {
+        let retry_after = header::HeaderValue::from_static("5");
+        let delay = Client::backoff_delay(3, Some(&retry_after));
+        assert_eq!(delay.as_secs(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_9_proppatch() {
+        let webdav_client = get_client();
+        let result = webdav_client
+            .proppatch(
+                get_server_path("rustydav/test.txt").as_str(),
+                &[("author", "rustydav")],
+                &[],
+            )
+            .await;
+
+        assert_eq!(result.is_ok(), true);
+    }
 }
@@ -0,0 +1,52 @@
+//! Transport compression for uploads and downloads
+//!
+//! Only gzip is implemented today; adding another codec is a matter of adding a variant here
+//! and a branch in [`Compression::content_encoding`], [`Compression::encode`],
+//! [`Compression::decode`] and [`Compression::from_content_encoding`].
+
+use async_compression::tokio::bufread::{GzipDecoder, GzipEncoder};
+use std::pin::Pin;
+use tokio::io::{AsyncRead, BufReader};
+
+/// Codec applied to a request/response body via `Content-Encoding`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+}
+
+impl Compression {
+    pub(crate) fn content_encoding(&self) -> &'static str {
+        match self {
+            Compression::Gzip => "gzip",
+        }
+    }
+
+    /// Wraps `reader` in a streaming encoder for this codec.
+    pub(crate) fn encode<R>(&self, reader: R) -> Pin<Box<dyn AsyncRead + Send>>
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        match self {
+            Compression::Gzip => Box::pin(GzipEncoder::new(BufReader::new(reader))),
+        }
+    }
+
+    /// Wraps `reader` in a streaming decoder for this codec.
+    pub(crate) fn decode<R>(&self, reader: R) -> Pin<Box<dyn AsyncRead + Send>>
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        match self {
+            Compression::Gzip => Box::pin(GzipDecoder::new(BufReader::new(reader))),
+        }
+    }
+
+    /// Matches a `Content-Encoding` header value against a known codec.
+    pub(crate) fn from_content_encoding(value: &str) -> Option<Self> {
+        if value.eq_ignore_ascii_case("gzip") {
+            Some(Compression::Gzip)
+        } else {
+            None
+        }
+    }
+}
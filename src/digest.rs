@@ -0,0 +1,294 @@
+//! HTTP Digest authentication (RFC 7616, `qop=auth` subset)
+//!
+//! Only enough of the scheme is implemented to satisfy WebDAV servers that
+//! reject Basic auth: MD5/MD5-sess `HA1`/`HA2` hashing with a single `auth` qop value.
+
+use crate::error::Error;
+
+/// The `algorithm` a Digest challenge asked for. Servers that omit `algorithm` entirely mean
+/// plain `MD5`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DigestAlgorithm {
+    Md5,
+    Md5Sess,
+}
+
+/// A parsed `WWW-Authenticate: Digest ...` challenge, cached per [`Client`](crate::Client)
+/// so later requests can skip the initial 401 round-trip.
+#[derive(Debug, Clone)]
+pub(crate) struct DigestChallenge {
+    pub realm: String,
+    pub nonce: String,
+    pub qop: Option<String>,
+    pub opaque: Option<String>,
+    /// The raw `algorithm` token from the challenge, if the server sent one.
+    pub algorithm: Option<String>,
+}
+
+impl DigestChallenge {
+    /// Parses the contents of a `WWW-Authenticate` header whose scheme is `Digest`.
+    pub fn parse(header_value: &str) -> Option<Self> {
+        let (scheme, rest) = header_value.trim().split_once(char::is_whitespace)?;
+        if !scheme.eq_ignore_ascii_case("Digest") {
+            return None;
+        }
+        let rest = rest.trim_start();
+
+        let mut realm = None;
+        let mut nonce = None;
+        let mut qop = None;
+        let mut opaque = None;
+        let mut algorithm = None;
+
+        for part in split_params(rest) {
+            let (key, value) = part.split_once('=')?;
+            let key = key.trim();
+            let value = value.trim().trim_matches('"');
+            match key {
+                "realm" => realm = Some(value.to_owned()),
+                "nonce" => nonce = Some(value.to_owned()),
+                "qop" => qop = Some(value.split(',').next().unwrap_or(value).trim().to_owned()),
+                "opaque" => opaque = Some(value.to_owned()),
+                "algorithm" => algorithm = Some(value.to_owned()),
+                _ => {}
+            }
+        }
+
+        Some(DigestChallenge {
+            realm: realm?,
+            nonce: nonce?,
+            qop,
+            opaque,
+            algorithm,
+        })
+    }
+
+    /// Resolves the raw `algorithm` token into one this client knows how to compute, or an
+    /// [`Error::Auth`] naming the unsupported algorithm (e.g. `SHA-256`).
+    fn resolve_algorithm(&self) -> Result<DigestAlgorithm, Error> {
+        match self.algorithm.as_deref() {
+            None => Ok(DigestAlgorithm::Md5),
+            Some(value) if value.eq_ignore_ascii_case("MD5") => Ok(DigestAlgorithm::Md5),
+            Some(value) if value.eq_ignore_ascii_case("MD5-sess") => Ok(DigestAlgorithm::Md5Sess),
+            Some(other) => Err(Error::Auth(format!("unsupported Digest algorithm: {other}"))),
+        }
+    }
+}
+
+/// Splits a comma-separated list of `key=value` pairs, ignoring commas inside quotes.
+fn split_params(src: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut in_quotes = false;
+    let mut start = 0;
+    for (i, c) in src.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => {
+                parts.push(src[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    let last = src[start..].trim();
+    if !last.is_empty() {
+        parts.push(last);
+    }
+    parts
+}
+
+/// Generates a random client nonce, sent back to the server as `cnonce`.
+pub(crate) fn client_nonce() -> String {
+    format!("{:016x}", rand::random::<u64>())
+}
+
+/// Builds the `Authorization: Digest ...` header value for `qop=auth`.
+///
+/// Fails with [`Error::Auth`] if the challenge asked for an `algorithm` other than `MD5` or
+/// `MD5-sess`, or for a `qop` other than `auth`.
+pub(crate) fn authorization_header(
+    challenge: &DigestChallenge,
+    username: &str,
+    password: &str,
+    method: &str,
+    uri: &str,
+    nonce_count: u32,
+    cnonce: &str,
+) -> Result<String, Error> {
+    let algorithm = challenge.resolve_algorithm()?;
+    if let Some(qop) = challenge.qop.as_deref().filter(|qop| !qop.eq_ignore_ascii_case("auth")) {
+        return Err(Error::Auth(format!("unsupported Digest qop: {qop}")));
+    }
+    let plain_ha1 = md5_hex(&format!("{username}:{}:{password}", challenge.realm));
+    let ha1 = match algorithm {
+        DigestAlgorithm::Md5 => plain_ha1,
+        DigestAlgorithm::Md5Sess => {
+            md5_hex(&format!("{plain_ha1}:{}:{cnonce}", challenge.nonce))
+        }
+    };
+    let ha2 = md5_hex(&format!("{method}:{uri}"));
+    let nc = format!("{nonce_count:08x}");
+
+    let response = match &challenge.qop {
+        Some(qop) => md5_hex(&format!(
+            "{ha1}:{}:{nc}:{cnonce}:{qop}:{ha2}",
+            challenge.nonce
+        )),
+        None => md5_hex(&format!("{ha1}:{}:{ha2}", challenge.nonce)),
+    };
+
+    let mut header = format!(
+        "Digest username=\"{username}\", realm=\"{}\", nonce=\"{}\", uri=\"{uri}\", response=\"{response}\"",
+        challenge.realm, challenge.nonce
+    );
+    if let Some(qop) = &challenge.qop {
+        header.push_str(&format!(", qop={qop}, nc={nc}, cnonce=\"{cnonce}\""));
+    }
+    if let Some(opaque) = &challenge.opaque {
+        header.push_str(&format!(", opaque=\"{opaque}\""));
+    }
+    if let Some(algorithm) = &challenge.algorithm {
+        header.push_str(&format!(", algorithm={algorithm}"));
+    }
+    Ok(header)
+}
+
+fn md5_hex(input: &str) -> String {
+    format!("{:x}", md5::compute(input.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_reads_a_typical_apache_challenge() {
+        let header = r#"Digest realm="example.com", qop="auth", nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093", opaque="5ccc069c403ebaf9f0171e9517f40e41""#;
+        let challenge = DigestChallenge::parse(header).unwrap();
+
+        assert_eq!(challenge.realm, "example.com");
+        assert_eq!(challenge.nonce, "dcd98b7102dd2f0e8b11d0f600bfb0c093");
+        assert_eq!(challenge.qop.as_deref(), Some("auth"));
+        assert_eq!(challenge.opaque.as_deref(), Some("5ccc069c403ebaf9f0171e9517f40e41"));
+    }
+
+    #[test]
+    fn parse_handles_a_quoted_comma_inside_qop() {
+        // Some servers send qop as a quoted, comma-separated list of options.
+        let header = r#"Digest realm="example.com", qop="auth,auth-int", nonce="abc123""#;
+        let challenge = DigestChallenge::parse(header).unwrap();
+
+        assert_eq!(challenge.qop.as_deref(), Some("auth"));
+    }
+
+    #[test]
+    fn parse_requires_realm_and_nonce() {
+        assert!(DigestChallenge::parse(r#"Digest qop="auth""#).is_none());
+        assert!(DigestChallenge::parse(r#"Digest realm="example.com""#).is_none());
+    }
+
+    #[test]
+    fn parse_rejects_non_digest_schemes() {
+        assert!(DigestChallenge::parse(r#"Basic realm="example.com""#).is_none());
+    }
+
+    #[test]
+    fn authorization_header_includes_qop_fields_when_present() {
+        let challenge = DigestChallenge {
+            realm: "example.com".to_string(),
+            nonce: "abc123".to_string(),
+            qop: Some("auth".to_string()),
+            opaque: Some("xyz".to_string()),
+            algorithm: None,
+        };
+
+        let header = authorization_header(
+            &challenge,
+            "alice",
+            "secret",
+            "GET",
+            "/private/file.txt",
+            1,
+            "cnonce123",
+        )
+        .unwrap();
+
+        assert!(header.starts_with("Digest username=\"alice\""));
+        assert!(header.contains(r#"uri="/private/file.txt""#));
+        assert!(header.contains("qop=auth"));
+        assert!(header.contains("nc=00000001"));
+        assert!(header.contains(r#"cnonce="cnonce123""#));
+        assert!(header.contains(r#"opaque="xyz""#));
+    }
+
+    #[test]
+    fn authorization_header_uses_md5_sess_when_requested() {
+        let challenge = DigestChallenge {
+            realm: "example.com".to_string(),
+            nonce: "abc123".to_string(),
+            qop: Some("auth".to_string()),
+            opaque: None,
+            algorithm: Some("MD5-sess".to_string()),
+        };
+
+        let header = authorization_header(
+            &challenge,
+            "alice",
+            "secret",
+            "GET",
+            "/private/file.txt",
+            1,
+            "cnonce123",
+        )
+        .unwrap();
+
+        assert!(header.contains("algorithm=MD5-sess"));
+
+        // Sanity check that the sess algorithm actually changes the response compared to plain
+        // MD5 (i.e. HA1 is genuinely folded over nonce/cnonce rather than ignored).
+        let plain_challenge = DigestChallenge {
+            algorithm: Some("MD5".to_string()),
+            ..challenge.clone()
+        };
+        let plain_header = authorization_header(
+            &plain_challenge,
+            "alice",
+            "secret",
+            "GET",
+            "/private/file.txt",
+            1,
+            "cnonce123",
+        )
+        .unwrap();
+        let response_of = |header: &str| {
+            header
+                .split("response=\"")
+                .nth(1)
+                .and_then(|rest| rest.split('"').next())
+                .map(str::to_owned)
+        };
+        assert_ne!(response_of(&header), response_of(&plain_header));
+    }
+
+    #[test]
+    fn authorization_header_rejects_unsupported_algorithms() {
+        let challenge = DigestChallenge {
+            realm: "example.com".to_string(),
+            nonce: "abc123".to_string(),
+            qop: Some("auth".to_string()),
+            opaque: None,
+            algorithm: Some("SHA-256".to_string()),
+        };
+
+        let result = authorization_header(
+            &challenge,
+            "alice",
+            "secret",
+            "GET",
+            "/private/file.txt",
+            1,
+            "cnonce123",
+        );
+        assert!(matches!(result, Err(Error::Auth(_))));
+    }
+}
@@ -0,0 +1,66 @@
+use std::fmt;
+
+/// Errors produced by this crate's WebDAV operations.
+///
+/// Replaces the previous mix of `reqwest::Error` (network methods) and `String` (XML parsing)
+/// so callers can match on one type regardless of where a request failed.
+#[derive(Debug)]
+pub enum Error {
+    /// The underlying HTTP request failed (connection, TLS, timeout, ...).
+    Transport(reqwest::Error),
+    /// A response body could not be parsed into the expected shape.
+    Xml(String),
+    /// The server answered with a 4xx/5xx status.
+    InvalidStatus { code: reqwest::StatusCode, body: String },
+    /// Authentication could not be completed, e.g. the server's Digest challenge asked for an
+    /// unsupported algorithm.
+    Auth(String),
+    /// A path argument could not be parsed as a URL.
+    Url(url::ParseError),
+    /// Reading the response body or writing it to its destination failed.
+    Io(std::io::Error),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Transport(e) => write!(f, "request failed: {e}"),
+            Error::Xml(message) => write!(f, "failed to parse WebDAV XML: {message}"),
+            Error::InvalidStatus { code, body } => {
+                write!(f, "server responded with {code}: {body}")
+            }
+            Error::Auth(message) => write!(f, "authentication failed: {message}"),
+            Error::Url(e) => write!(f, "invalid path: {e}"),
+            Error::Io(e) => write!(f, "i/o error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Transport(e) => Some(e),
+            Error::Url(e) => Some(e),
+            Error::Io(e) => Some(e),
+            Error::Xml(_) | Error::InvalidStatus { .. } | Error::Auth(_) => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        Error::Transport(e)
+    }
+}
+
+impl From<url::ParseError> for Error {
+    fn from(e: url::ParseError) -> Self {
+        Error::Url(e)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
@@ -1,4 +1,13 @@
-use quick_xml::name::QName;
+use crate::error::Error;
+use quick_xml::events::Event;
+use quick_xml::name::{Namespace, ResolveResult};
+use quick_xml::reader::NsReader;
+
+const DAV_NS: &[u8] = b"DAV:";
+
+fn xml_err(e: impl ToString) -> Error {
+    Error::Xml(e.to_string())
+}
 
 #[derive(Debug, Clone)]
 pub struct FileInfo {
@@ -11,71 +20,308 @@ pub struct FileInfo {
     pub modified_date: String,
 }
 
-pub fn parse_xml(src: &str) -> Result<Vec<FileInfo>, String> {
+/// Whether a resolved element is `DAV:`'s `local_name`, regardless of which prefix
+/// the server bound it to (`D:`, `lp1:`, a bare default namespace, ...).
+fn is_dav(ns: &ResolveResult, local_name: &[u8], expected: &[u8]) -> bool {
+    matches!(ns, ResolveResult::Bound(Namespace(DAV_NS))) && local_name == expected
+}
+
+/// Parses a WebDAV PROPFIND multistatus response into a list of [`FileInfo`].
+///
+/// Elements are matched by resolving the `DAV:` namespace rather than assuming a literal
+/// `D:`/`lp1:` prefix, so this works against Apache mod_dav, Nginx, sabre/dav, IIS and
+/// nextcloud alike. `propstat` blocks whose `status` is not `200` (e.g. a `404` for a property
+/// the server doesn't have) are ignored.
+pub fn parse_xml(src: &str) -> Result<Vec<FileInfo>, Error> {
     let mut files = Vec::new();
-    let mut reader = quick_xml::Reader::from_str(src);
-    reader.trim_text(true);
+    let mut reader = NsReader::from_str(src);
+    reader.config_mut().trim_text(true);
+
     loop {
-        match reader.read_event() {
-            Ok(quick_xml::events::Event::Start(ref e)) if e.name() == QName(b"D:response") => {
-                let mut file = FileInfo {
-                    path: String::new(),
-                    name: String::new(),
-                    size: 0,
-                    is_dir: true,
-                    file_type: String::new(),
-                    create_date: String::new(),
-                    modified_date: String::new(),
-                };
-                loop {
-                    match reader.read_event() {
-                        Ok(quick_xml::events::Event::Start(ref e)) => {
-                            if e.name() == QName(b"D:href") {
-                                file.path = reader
-                                    .read_text(e.name())
-                                    .map_err(|x| x.to_string())?
-                                    .to_string();
-                                file.name = file.path.split('/').last().unwrap_or("").to_string();
-                                file.file_type =
-                                    file.name.split('.').last().unwrap_or("").to_string();
-                            } else if e.name() == QName(b"lp1:getcontentlength") {
-                                file.size = reader
-                                    .read_text(e.name())
-                                    .map_err(|x| x.to_string())?
-                                    .to_string()
-                                    .parse::<u64>()
-                                    .map_err(|_| "Failed to parse size")?;
-                                file.is_dir = false;
-                            } else if e.name() == QName(b"lp1:creationdate") {
-                                file.create_date = reader
-                                    .read_text(e.name())
-                                    .map_err(|x| x.to_string())?
-                                    .to_string();
-                            } else if e.name() == QName(b"lp1:getlastmodified") {
-                                file.modified_date = reader
-                                    .read_text(e.name())
-                                    .map_err(|x| x.to_string())?
-                                    .to_string();
-                            }
-                        }
-                        Ok(quick_xml::events::Event::End(ref e)) => {
-                            if e.name() == QName(b"D:response") {
-                                files.push(file);
-                                break;
-                            }
-                        }
-                        Ok(quick_xml::events::Event::Eof) => return Ok(files),
-                        Err(e) => return Err(e.to_string()),
-                        _ => (),
+        match reader.read_resolved_event().map_err(xml_err)? {
+            (ns, Event::Start(ref e)) if is_dav(&ns, e.local_name().into_inner(), b"response") => {
+                files.push(parse_response(&mut reader)?);
+            }
+            (_, Event::Eof) => break,
+            _ => {}
+        }
+    }
+
+    Ok(files)
+}
+
+/// Parses the contents of a single `<response>` element, positioned right after its start tag.
+fn parse_response(reader: &mut NsReader<&[u8]>) -> Result<FileInfo, Error> {
+    let mut file = FileInfo {
+        path: String::new(),
+        name: String::new(),
+        size: 0,
+        is_dir: false,
+        file_type: String::new(),
+        create_date: String::new(),
+        modified_date: String::new(),
+    };
+
+    // Properties found in the propstat currently being read; only merged into `file` once
+    // that propstat's `status` is known to be a success.
+    let mut pending = FileInfo {
+        is_dir: false,
+        ..file.clone()
+    };
+    let mut in_propstat = false;
+    let mut propstat_ok = true;
+
+    loop {
+        match reader.read_resolved_event().map_err(xml_err)? {
+            (ns, Event::Start(ref e)) => {
+                let local = e.local_name().into_inner().to_vec();
+                if is_dav(&ns, &local, b"href") {
+                    file.path = reader.read_text(e.name()).map_err(xml_err)?.to_string();
+                    file.name = file.path.trim_end_matches('/').split('/').next_back().unwrap_or("").to_string();
+                    file.file_type = file.name.split('.').next_back().unwrap_or("").to_string();
+                } else if is_dav(&ns, &local, b"propstat") {
+                    in_propstat = true;
+                    propstat_ok = true;
+                    pending = FileInfo {
+                        is_dir: false,
+                        ..file.clone()
+                    };
+                } else if is_dav(&ns, &local, b"status") && in_propstat {
+                    let status = reader.read_text(e.name()).map_err(xml_err)?.to_string();
+                    propstat_ok = status.contains(" 200 ") || status.trim_end().ends_with(" 200");
+                } else if is_dav(&ns, &local, b"getcontentlength") {
+                    let text = reader.read_text(e.name()).map_err(xml_err)?;
+                    pending.size = text.parse::<u64>().map_err(|_| Error::Xml("failed to parse size".to_string()))?;
+                } else if is_dav(&ns, &local, b"getcontenttype") {
+                    pending.file_type = reader.read_text(e.name()).map_err(xml_err)?.to_string();
+                } else if is_dav(&ns, &local, b"creationdate") {
+                    pending.create_date = reader.read_text(e.name()).map_err(xml_err)?.to_string();
+                } else if is_dav(&ns, &local, b"getlastmodified") {
+                    pending.modified_date = reader.read_text(e.name()).map_err(xml_err)?.to_string();
+                } else if is_dav(&ns, &local, b"resourcetype") {
+                    pending.is_dir = element_has_child(reader, e.name(), b"collection")?;
+                }
+            }
+            (ns, Event::End(ref e)) => {
+                let local = e.local_name().into_inner().to_vec();
+                if is_dav(&ns, &local, b"propstat") {
+                    in_propstat = false;
+                    if propstat_ok {
+                        merge_propstat(&mut file, &pending);
                     }
+                } else if is_dav(&ns, &local, b"response") {
+                    return Ok(file);
                 }
             }
-            Ok(quick_xml::events::Event::Start(ref e)) => {}
-            Ok(quick_xml::events::Event::Eof) => break,
-            Err(e) => return Err(e.to_string()),
-            _ => (),
+            (_, Event::Eof) => return Ok(file),
+            _ => {}
         }
     }
+}
 
-    Ok(files)
+/// Scans forward through `name`'s children looking for a `DAV:` element whose local name is
+/// `child_local_name` (e.g. `<collection/>` inside `<resourcetype>`), leaving the
+/// reader positioned right after `name`'s matching end tag either way.
+fn element_has_child(
+    reader: &mut NsReader<&[u8]>,
+    name: quick_xml::name::QName,
+    child_local_name: &[u8],
+) -> Result<bool, Error> {
+    let mut found = false;
+    let mut depth = 0;
+    loop {
+        match reader.read_resolved_event().map_err(xml_err)? {
+            (ns, Event::Empty(ref e)) => {
+                if is_dav(&ns, e.local_name().into_inner(), child_local_name) {
+                    found = true;
+                }
+            }
+            (ns, Event::Start(ref e)) => {
+                if is_dav(&ns, e.local_name().into_inner(), child_local_name) {
+                    found = true;
+                }
+                depth += 1;
+            }
+            (_, Event::End(ref e)) => {
+                if e.name() == name && depth == 0 {
+                    return Ok(found);
+                }
+                depth -= 1;
+            }
+            (_, Event::Eof) => return Ok(found),
+            _ => {}
+        }
+    }
+}
+
+/// Pulls the opaque token out of a `LOCK` response's `<D:locktoken><D:href>...</D:href></D:locktoken>`
+pub(crate) fn parse_lock_token(xml: &str) -> Option<String> {
+    let mut reader = NsReader::from_str(xml);
+    reader.config_mut().trim_text(true);
+    let mut in_locktoken = false;
+
+    loop {
+        match reader.read_resolved_event().ok()? {
+            (ns, Event::Start(ref e)) => {
+                let local = e.local_name().into_inner().to_vec();
+                if is_dav(&ns, &local, b"locktoken") {
+                    in_locktoken = true;
+                } else if in_locktoken && is_dav(&ns, &local, b"href") {
+                    return reader.read_text(e.name()).ok().map(|t| t.trim().to_string());
+                }
+            }
+            (ns, Event::End(ref e)) => {
+                if is_dav(&ns, e.local_name().into_inner(), b"locktoken") {
+                    in_locktoken = false;
+                }
+            }
+            (_, Event::Eof) => return None,
+            _ => {}
+        }
+    }
+}
+
+/// Copies the fields a propstat block may carry over to the response-level `FileInfo`, without
+/// clobbering fields a prior (successful) propstat already populated.
+fn merge_propstat(file: &mut FileInfo, pending: &FileInfo) {
+    if pending.size != 0 {
+        file.size = pending.size;
+    }
+    if !pending.file_type.is_empty() {
+        file.file_type = pending.file_type.clone();
+    }
+    if !pending.create_date.is_empty() {
+        file.create_date = pending.create_date.clone();
+    }
+    if !pending.modified_date.is_empty() {
+        file.modified_date = pending.modified_date.clone();
+    }
+    if pending.is_dir {
+        file.is_dir = true;
+    }
+    if file.file_type.is_empty() {
+        file.file_type = file.name.split('.').next_back().unwrap_or("").to_string();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A trimmed-down sabre/dav-style multistatus response: a collection and a plain file,
+    // with the `D:` prefix and a `404` propstat for a property the server doesn't have.
+    const SABRE_PROPFIND: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<D:multistatus xmlns:D="DAV:">
+    <D:response>
+        <D:href>/files/docs/</D:href>
+        <D:propstat>
+            <D:prop>
+                <D:resourcetype><D:collection/></D:resourcetype>
+                <D:getlastmodified>Mon, 01 Jan 2024 00:00:00 GMT</D:getlastmodified>
+            </D:prop>
+            <D:status>HTTP/1.1 200 OK</D:status>
+        </D:propstat>
+        <D:propstat>
+            <D:prop>
+                <D:quota-used-bytes/>
+            </D:prop>
+            <D:status>HTTP/1.1 404 Not Found</D:status>
+        </D:propstat>
+    </D:response>
+    <D:response>
+        <D:href>/files/docs/report.txt</D:href>
+        <D:propstat>
+            <D:prop>
+                <D:resourcetype/>
+                <D:getcontentlength>1234</D:getcontentlength>
+                <D:getcontenttype>text/plain</D:getcontenttype>
+                <D:creationdate>2024-01-01T00:00:00Z</D:creationdate>
+                <D:getlastmodified>Mon, 01 Jan 2024 00:00:00 GMT</D:getlastmodified>
+            </D:prop>
+            <D:status>HTTP/1.1 200 OK</D:status>
+        </D:propstat>
+    </D:response>
+</D:multistatus>"#;
+
+    // The same response, but with a bare default namespace instead of the `D:` prefix, like
+    // some nginx-fronted WebDAV setups emit.
+    const NGINX_STYLE_PROPFIND: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<multistatus xmlns="DAV:">
+    <response>
+        <href>/data/image.png</href>
+        <propstat>
+            <prop>
+                <resourcetype/>
+                <getcontentlength>42</getcontentlength>
+            </prop>
+            <status>HTTP/1.1 200 OK</status>
+        </propstat>
+    </response>
+</multistatus>"#;
+
+    #[test]
+    fn parse_xml_reads_a_sabre_dav_response() {
+        let files = parse_xml(SABRE_PROPFIND).unwrap();
+        assert_eq!(files.len(), 2);
+
+        let dir = &files[0];
+        assert_eq!(dir.path, "/files/docs/");
+        assert_eq!(dir.name, "docs");
+        assert!(dir.is_dir);
+        assert_eq!(dir.modified_date, "Mon, 01 Jan 2024 00:00:00 GMT");
+
+        let file = &files[1];
+        assert_eq!(file.path, "/files/docs/report.txt");
+        assert_eq!(file.name, "report.txt");
+        assert!(!file.is_dir);
+        assert_eq!(file.size, 1234);
+        assert_eq!(file.file_type, "text/plain");
+        assert_eq!(file.create_date, "2024-01-01T00:00:00Z");
+    }
+
+    #[test]
+    fn parse_xml_ignores_failed_propstat_blocks() {
+        // The 404 propstat in the first response must not surface a `quota-used-bytes` field
+        // anywhere, and must not stop the successful 200 propstat from being merged in.
+        let files = parse_xml(SABRE_PROPFIND).unwrap();
+        assert!(files[0].is_dir);
+    }
+
+    #[test]
+    fn parse_xml_matches_a_bare_default_namespace() {
+        let files = parse_xml(NGINX_STYLE_PROPFIND).unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, "/data/image.png");
+        assert_eq!(files[0].size, 42);
+    }
+
+    #[test]
+    fn parse_xml_rejects_malformed_xml() {
+        assert!(parse_xml("<not-valid").is_err());
+    }
+
+    #[test]
+    fn parse_lock_token_reads_the_href_regardless_of_prefix() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<D:prop xmlns:D="DAV:">
+    <D:lockdiscovery>
+        <D:activelock>
+            <D:locktoken>
+                <D:href>opaquelocktoken:e71d4fae-5dec-22d6-fea5-00a0c91e6be4</D:href>
+            </D:locktoken>
+        </D:activelock>
+    </D:lockdiscovery>
+</D:prop>"#;
+
+        assert_eq!(
+            parse_lock_token(xml).as_deref(),
+            Some("opaquelocktoken:e71d4fae-5dec-22d6-fea5-00a0c91e6be4")
+        );
+    }
+
+    #[test]
+    fn parse_lock_token_returns_none_without_a_locktoken() {
+        let xml = r#"<D:prop xmlns:D="DAV:"></D:prop>"#;
+        assert_eq!(parse_lock_token(xml), None);
+    }
 }
@@ -0,0 +1,18 @@
+//! rustydav_async
+//!
+//! An async WebDAV client built on top of [`reqwest`].
+//!
+//! See [`client::Client`] for the list of supported operations.
+
+mod digest;
+mod prelude;
+
+pub mod client;
+pub mod compression;
+pub mod error;
+pub mod file;
+
+pub use client::Client;
+pub use compression::Compression;
+pub use error::Error;
+pub use file::FileInfo;
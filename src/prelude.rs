@@ -0,0 +1,4 @@
+pub use crate::compression::Compression;
+pub use crate::error::Error;
+pub use crate::file::FileInfo;
+pub use reqwest::{header, Body, Method, RequestBuilder, Response, Url};